@@ -1,6 +1,6 @@
 use super::*;
 use crate::Color;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum Rotation {
@@ -40,11 +40,43 @@ impl Rotation {
     }
 }
 
+/// A closure that attempts to (re-)connect a hotplugged device, given the message callback it
+/// should deliver `Press`/`Release` events to. Boxed so [`add_by_guess_hotplug`](CanvasLayout::add_by_guess_hotplug)
+/// doesn't have to carry its `E: DeviceCanvasTrait` type parameter around inside [`LayoutDevice`].
+type Reconnector<'a> = Box<
+    dyn FnMut(
+            Box<dyn Fn(CanvasMessage) + Send + Sync + 'static>,
+        ) -> Result<Box<dyn Canvas + 'a>, crate::MidiError>
+        + 'a,
+>;
+
+/// A device's keyword and reconnect closure, kept around for as long as the device exists - even
+/// while it's [`Bound`](DeviceBinding::Bound) - so [`CanvasLayout::disconnect`] can demote it back
+/// to [`Pending`](DeviceBinding::Pending) and [`CanvasLayout::reconnect_pending`] can bind it again
+/// later. Only devices added through [`CanvasLayout::add_by_guess_hotplug`] have one.
+struct Hotplug<'a> {
+    keyword: &'static str,
+    reconnect: Reconnector<'a>,
+}
+
+enum DeviceBinding<'a> {
+    /// Hardware is attached and receiving flushes.
+    Bound(Box<dyn Canvas + 'a>),
+    /// No hardware is attached. Flushes to this slot are silently dropped until
+    /// [`CanvasLayout::reconnect_pending`] successfully rebinds it.
+    Pending,
+}
+
 struct LayoutDevice<'a> {
-    canvas: Box<dyn Canvas + 'a>,
+    canvas: DeviceBinding<'a>,
     rotation: Rotation,
     x: u32,
     y: u32,
+    // This device's own (unrotated) `(width, height)`, known up-front even while `canvas` is
+    // still `Pending` (no hardware to ask yet) so `CanvasLayout::bounding_box` can still account
+    // for it.
+    size: (u32, u32),
+    hotplug: Option<Hotplug<'a>>,
 }
 
 fn to_local(x: u32, y: u32, rot: Rotation, x_offset: u32, y_offset: u32) -> (u32, u32) {
@@ -127,6 +159,9 @@ fn transform_color(color: Color, source: f32, target: f32) -> Color {
 pub struct CanvasLayout<'a> {
     devices: Vec<LayoutDevice<'a>>,
     coordinate_map: HashMap<(u32, u32), Pixel>, // we need to store some stuff for each pixel
+    // Coordinates that were written to since the last flush. `flush` only has to look at these,
+    // instead of walking the entire (possibly huge, multi-device) coordinate map every frame.
+    dirty: HashSet<(u32, u32)>,
     callback: std::sync::Arc<dyn Fn(CanvasMessage) + Send + Sync + 'static>,
     light_threshold: f32,
 }
@@ -138,6 +173,7 @@ impl<'a> CanvasLayout<'a> {
         Self {
             devices: Vec::new(),
             coordinate_map: HashMap::new(),
+            dirty: HashSet::new(),
             callback: std::sync::Arc::new(callback),
             light_threshold: 1.0 / 4.0, // good default value? I have, like, no idea
         }
@@ -221,6 +257,7 @@ impl<'a> CanvasLayout<'a> {
                     color_old: canvas[pad],
                 },
             );
+            self.dirty.insert(translated_coords);
 
             // check for overlap
             if let Some(Pixel {
@@ -236,10 +273,12 @@ impl<'a> CanvasLayout<'a> {
         }
 
         let layout_device = LayoutDevice {
-            canvas: Box::new(canvas),
+            size: canvas.bounding_box(),
+            canvas: DeviceBinding::Bound(Box::new(canvas)),
             rotation,
             x: x_offset,
             y: y_offset,
+            hotplug: None,
         };
         self.devices.push(layout_device);
 
@@ -277,6 +316,229 @@ impl<'a> CanvasLayout<'a> {
     ) -> Result<(), crate::MidiError> {
         self.add(x, y, rotation, DeviceCanvas::<E::Spec>::guess)
     }
+
+    /// Like [`Self::add_by_guess_rotated`], but tolerates the hardware not being found right now.
+    ///
+    /// If guessing succeeds immediately, this behaves like `add_by_guess_rotated`, except that the
+    /// device keeps its keyword, so [`Self::disconnect`] and [`Self::reconnect_pending`] work on it
+    /// if it's unplugged later. If it
+    /// doesn't - say, the Launchpad is unplugged - the slot is still registered as "pending", with
+    /// its `bounding_box` pixels already reserved in the coordinate map (so [`Self::bounding_box`]
+    /// and writes against this region work right away, same as a connected device - they're just
+    /// silently dropped on flush) until [`Self::reconnect_pending`] is called with a matching
+    /// keyword (e.g. once a [`DeviceMonitor`](crate::DeviceMonitor) reports the port has appeared)
+    /// and successfully rebinds it in place.
+    ///
+    /// `bounding_box` has to be passed explicitly because there's no hardware to ask yet - use the
+    /// same `(width, height)` that `E`'s connected `Canvas::bounding_box()` would report.
+    pub fn add_by_guess_hotplug<E: 'a + DeviceCanvasTrait>(
+        &mut self,
+        x_offset: u32,
+        y_offset: u32,
+        rotation: Rotation,
+        bounding_box: (u32, u32),
+    ) -> Result<(), crate::MidiError> {
+        let hotplug = Hotplug {
+            keyword: <E::Spec as crate::OutputDevice>::MIDI_DEVICE_KEYWORD,
+            reconnect: Box::new(|callback| {
+                DeviceCanvas::<E::Spec>::guess(callback)
+                    .map(|canvas| Box::new(canvas) as Box<dyn Canvas + 'a>)
+            }),
+        };
+
+        match self.add(x_offset, y_offset, rotation, DeviceCanvas::<E::Spec>::guess) {
+            Ok(()) => {
+                self.devices.last_mut().expect("add() just pushed this device").hotplug = Some(hotplug);
+                Ok(())
+            }
+            Err(_) => {
+                let index = self.devices.len();
+                let (width, height) = bounding_box;
+
+                for local_y in 0..height {
+                    for local_x in 0..width {
+                        let translated_coords =
+                            to_global(local_x, local_y, rotation, x_offset, y_offset);
+                        let old_value = self.coordinate_map.insert(
+                            translated_coords,
+                            Pixel { device_index: index, color_new: Color::BLACK, color_old: Color::BLACK },
+                        );
+
+                        // check for overlap, same as `add()` does for connected devices
+                        if let Some(Pixel { device_index: old_device_index, .. }) = old_value {
+                            panic!(
+                                "Found overlap at ({}|{})! with canvas {} while adding pending canvas {} to layout (zero-indexed)",
+                                translated_coords.0, translated_coords.1, old_device_index, index,
+                            );
+                        }
+                    }
+                }
+
+                self.devices.push(LayoutDevice {
+                    canvas: DeviceBinding::Pending,
+                    hotplug: Some(hotplug),
+                    rotation,
+                    x: x_offset,
+                    y: y_offset,
+                    size: bounding_box,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Tries to bind the first still-pending slot (added via [`Self::add_by_guess_hotplug`])
+    /// whose keyword matches `keyword`, rebinding it in place without disturbing any other
+    /// device's index. Returns whether a slot was rebound.
+    ///
+    /// Its pixels are already in the coordinate map (reserved back when it was added), so whatever
+    /// colors were set on them while pending are kept as-is rather than reset - call
+    /// [`Self::force_full_flush`] afterwards to push that picture to the now-attached hardware.
+    ///
+    /// Typically called in response to a [`DeviceEvent::Connected`](crate::DeviceEvent::Connected)
+    /// from a [`DeviceMonitor`](crate::DeviceMonitor).
+    pub fn reconnect_pending(&mut self, keyword: &str) -> Result<bool, crate::MidiError> {
+        let callback = self.callback.clone();
+
+        for index in 0..self.devices.len() {
+            let (rotation, x_offset, y_offset) = (
+                self.devices[index].rotation,
+                self.devices[index].x,
+                self.devices[index].y,
+            );
+
+            let is_pending_with_keyword = matches!(self.devices[index].canvas, DeviceBinding::Pending)
+                && matches!(&self.devices[index].hotplug, Some(hotplug) if hotplug.keyword == keyword);
+            if !is_pending_with_keyword {
+                continue;
+            }
+
+            let callback = callback.clone();
+            let wrapped_callback: Box<dyn Fn(CanvasMessage) + Send + Sync + 'static> =
+                Box::new(move |msg| {
+                    let (x, y) = to_global(msg.x(), msg.y(), rotation, x_offset, y_offset);
+                    match msg {
+                        CanvasMessage::Press { .. } => (callback)(CanvasMessage::Press { x, y }),
+                        CanvasMessage::Release { .. } => (callback)(CanvasMessage::Release { x, y }),
+                    }
+                });
+
+            let reconnect = &mut self.devices[index]
+                .hotplug
+                .as_mut()
+                .expect("just checked this device has a hotplug slot")
+                .reconnect;
+            let canvas = (reconnect)(wrapped_callback)?;
+
+            // This slot's pixels are already in the coordinate map from `add_by_guess_hotplug` -
+            // leave their colors alone, they may have been written to while pending.
+            self.devices[index].canvas = DeviceBinding::Bound(canvas);
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Tries to find the first currently-[`Bound`](DeviceBinding::Bound) device (added through
+    /// [`Self::add_by_guess_hotplug`]) whose keyword matches `keyword`, and demotes it back to
+    /// "pending": its pixels stay exactly where they are in the coordinate map, but flushes to it
+    /// go back to being silently dropped until [`Self::reconnect_pending`] rebinds it. Returns
+    /// whether a device was demoted.
+    ///
+    /// Typically called in response to a
+    /// [`DeviceEvent::Disconnected`](crate::DeviceEvent::Disconnected) from a
+    /// [`DeviceMonitor`](crate::DeviceMonitor), so a layout reacts to a cable being pulled
+    /// instead of only ever finding out the hard way, from a failed [`Self::flush`].
+    pub fn disconnect(&mut self, keyword: &str) -> bool {
+        for device in &mut self.devices {
+            let matches = matches!(&device.hotplug, Some(hotplug) if hotplug.keyword == keyword)
+                && matches!(device.canvas, DeviceBinding::Bound(_));
+            if matches {
+                device.canvas = DeviceBinding::Pending;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Flushes every pixel in this layout, ignoring the dirty-tracking optimization that the
+    /// normal [`Canvas::flush`] relies on.
+    ///
+    /// Needed after a device was just (re-)added: its hardware has no idea what color its pixels
+    /// are supposed to be yet, so it must receive the full picture rather than just whatever
+    /// changed since the layout's last flush.
+    pub fn force_full_flush(&mut self) -> Result<(), crate::MidiError> {
+        self.flush_impl(true)
+    }
+
+    fn flush_impl(&mut self, force_full: bool) -> Result<(), crate::MidiError> {
+        let coords: Vec<(u32, u32)> = if force_full {
+            self.coordinate_map.keys().copied().collect()
+        } else {
+            self.dirty.drain().collect()
+        };
+
+        let mut touched_devices = HashSet::new();
+
+        for (global_x, global_y) in coords {
+            let pixel = match self.coordinate_map.get_mut(&(global_x, global_y)) {
+                Some(pixel) => pixel,
+                None => continue,
+            };
+
+            if !force_full && pixel.color_new == pixel.color_old {
+                continue;
+            }
+
+            let device = &mut self.devices[pixel.device_index];
+            if matches!(device.canvas, DeviceBinding::Pending) {
+                // No hardware attached - drop the write until reconnect_pending rebinds it.
+                continue;
+            }
+
+            let (local_x, local_y) = device.to_local(global_x, global_y);
+            let canvas = match &mut device.canvas {
+                DeviceBinding::Bound(canvas) => canvas,
+                DeviceBinding::Pending => unreachable!("just checked this slot is Bound"),
+            };
+
+            let transformed_color = transform_color(
+                pixel.color_new,
+                self.light_threshold,
+                canvas.lowest_visible_brightness(),
+            );
+
+            // A slot reserved while pending covers its whole bounding box, but the hardware bound
+            // to it later may lack some of those coordinates (e.g. a narrower control row).
+            match canvas.low_level_get_pending_mut(local_x, local_y) {
+                Some(slot) => *slot = transformed_color,
+                None => continue,
+            }
+
+            pixel.color_old = pixel.color_new;
+            touched_devices.insert(pixel.device_index);
+        }
+
+        for device_index in touched_devices {
+            let canvas = match &mut self.devices[device_index].canvas {
+                DeviceBinding::Bound(canvas) => canvas,
+                DeviceBinding::Pending => continue,
+            };
+
+            if canvas.flush().is_err() {
+                // Hardware stopped responding - e.g. the cable was pulled since the last flush.
+                // Demote back to `Pending` instead of bubbling this one device's error out of an
+                // otherwise-healthy multi-device flush. A device added through
+                // `add_by_guess_hotplug` can be bound again by `reconnect_pending` once it's back;
+                // any other device stays pending.
+                self.devices[device_index].canvas = DeviceBinding::Pending;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Canvas for CanvasLayout<'_> {
@@ -289,7 +551,9 @@ impl Canvas for CanvasLayout<'_> {
         let mut height = 0;
 
         for device in &self.devices {
-            let (device_width, device_height) = device.canvas.bounding_box();
+            // `size` is known even for a still-`Pending` device - its pixels are already reserved
+            // in the coordinate map, so it should still grow the bounds.
+            let (device_width, device_height) = device.size;
 
             width = u32::max(width, device_width);
             height = u32::max(height, device_height);
@@ -304,6 +568,14 @@ impl Canvas for CanvasLayout<'_> {
     }
 
     fn low_level_get_pending_mut(&mut self, x: u32, y: u32) -> Option<&mut Color> {
+        if !self.coordinate_map.contains_key(&(x, y)) {
+            return None;
+        }
+
+        // whoever calls this is about to (possibly) change the pixel - make sure it gets
+        // re-sent on the next flush
+        self.dirty.insert((x, y));
+
         // store the actual pixel color for possible retrieval later
         let pixel = self.coordinate_map.get_mut(&(x, y))?;
         Some(&mut pixel.color_new)
@@ -315,31 +587,181 @@ impl Canvas for CanvasLayout<'_> {
     }
 
     fn flush(&mut self) -> Result<(), crate::MidiError> {
-        for (&(global_x, global_y), pixel) in self.coordinate_map.iter_mut() {
-            let device = &mut self.devices[pixel.device_index];
+        self.flush_impl(false)
+    }
+}
 
-            let transformed_color = transform_color(
-                pixel.color_new,
-                self.light_threshold,
-                device.canvas.lowest_visible_brightness(),
-            );
+impl_traits_for_canvas!(CanvasLayout['a]);
 
-            let (local_x, local_y) = device.to_local(global_x, global_y);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// A tiny in-memory [`Canvas`], standing in for real MIDI hardware so
+    /// [`CanvasLayout`]'s dirty-tracking can be exercised without a device attached. Counts how
+    /// many times [`Canvas::flush`] actually ran, so tests can tell an unchanged flush was
+    /// skipped from one that wasn't.
+    struct TestCanvas {
+        width: u32,
+        height: u32,
+        pixels: HashMap<(u32, u32), Color>,
+        flush_count: Arc<Mutex<u32>>,
+    }
 
-            *device
-                .canvas
-                .low_level_get_pending_mut(local_x, local_y)
-                .unwrap() = transformed_color;
+    impl TestCanvas {
+        fn new(width: u32, height: u32, flush_count: Arc<Mutex<u32>>) -> Self {
+            let mut pixels = HashMap::new();
+            for y in 0..height {
+                for x in 0..width {
+                    pixels.insert((x, y), Color::BLACK);
+                }
+            }
+            Self { width, height, pixels, flush_count }
+        }
+    }
 
-            pixel.color_old = pixel.color_new;
+    impl Canvas for TestCanvas {
+        fn lowest_visible_brightness(&self) -> f32 {
+            0.0
         }
 
-        for device in &mut self.devices {
-            device.canvas.flush()?;
+        fn bounding_box(&self) -> (u32, u32) {
+            (self.width, self.height)
         }
 
-        Ok(())
+        fn low_level_get_pending(&self, x: u32, y: u32) -> Option<&Color> {
+            self.pixels.get(&(x, y))
+        }
+
+        fn low_level_get_pending_mut(&mut self, x: u32, y: u32) -> Option<&mut Color> {
+            self.pixels.get_mut(&(x, y))
+        }
+
+        fn low_level_get(&self, x: u32, y: u32) -> Option<&Color> {
+            self.pixels.get(&(x, y))
+        }
+
+        fn flush(&mut self) -> Result<(), crate::MidiError> {
+            *self.flush_count.lock().unwrap() += 1;
+            Ok(())
+        }
     }
-}
 
-impl_traits_for_canvas!(CanvasLayout['a]);
+    #[test]
+    fn flush_skips_pixels_that_did_not_change() {
+        let flush_count = Arc::new(Mutex::new(0));
+        let mut layout = CanvasLayout::new(|_| {});
+        {
+            let flush_count = flush_count.clone();
+            layout
+                .add(0, 0, Rotation::None, move |_callback| {
+                    Ok::<_, std::convert::Infallible>(TestCanvas::new(2, 2, flush_count))
+                })
+                .unwrap();
+        }
+
+        // First write actually changes the pixel, so the device's flush runs once.
+        *layout.low_level_get_pending_mut(0, 0).unwrap() = Color::RED;
+        layout.flush().unwrap();
+        assert_eq!(*flush_count.lock().unwrap(), 1);
+
+        // Flushing again with nothing newly written: the dirty set is empty, so the device's
+        // flush is skipped entirely.
+        layout.flush().unwrap();
+        assert_eq!(*flush_count.lock().unwrap(), 1);
+
+        // Writing the exact same color back still marks the pixel dirty, but `flush_impl` should
+        // notice `color_new == color_old` and skip sending it regardless.
+        *layout.low_level_get_pending_mut(0, 0).unwrap() = Color::RED;
+        layout.flush().unwrap();
+        assert_eq!(*flush_count.lock().unwrap(), 1);
+
+        // A genuinely different color is sent.
+        *layout.low_level_get_pending_mut(0, 0).unwrap() = Color::BLACK;
+        layout.flush().unwrap();
+        assert_eq!(*flush_count.lock().unwrap(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Found overlap")]
+    fn add_panics_on_coordinate_collision() {
+        let flush_count = Arc::new(Mutex::new(0));
+        let mut layout = CanvasLayout::new(|_| {});
+        layout
+            .add(0, 0, Rotation::None, {
+                let flush_count = flush_count.clone();
+                move |_callback| Ok::<_, std::convert::Infallible>(TestCanvas::new(2, 2, flush_count))
+            })
+            .unwrap();
+
+        // Second device's top-left corner lands on the first device's (1, 1) - same "Found overlap"
+        // panic that `add_by_guess_hotplug`'s pending-pixel reservation loop raises.
+        layout
+            .add(1, 1, Rotation::None, move |_callback| {
+                Ok::<_, std::convert::Infallible>(TestCanvas::new(2, 2, flush_count))
+            })
+            .unwrap();
+    }
+
+    /// Manually builds a hotplug-capable [`LayoutDevice`], bypassing [`CanvasLayout::add_by_guess_hotplug`]
+    /// (which needs a real, guessable `DeviceCanvasTrait` impl we don't have in a unit test), so the
+    /// `disconnect`/`reconnect_pending` state machine can be exercised directly.
+    fn push_pending_hotplug_device(layout: &mut CanvasLayout<'static>, keyword: &'static str) {
+        let index = layout.devices.len();
+        layout.coordinate_map.insert(
+            (0, 0),
+            Pixel { device_index: index, color_new: Color::BLACK, color_old: Color::BLACK },
+        );
+        layout.devices.push(LayoutDevice {
+            canvas: DeviceBinding::Pending,
+            hotplug: Some(Hotplug {
+                keyword,
+                reconnect: Box::new({
+                    let flush_count = Arc::new(Mutex::new(0));
+                    move |_callback| Ok(Box::new(TestCanvas::new(1, 1, flush_count.clone())) as Box<dyn Canvas>)
+                }),
+            }),
+            rotation: Rotation::None,
+            x: 0,
+            y: 0,
+            size: (1, 1),
+        });
+    }
+
+    #[test]
+    fn reconnect_pending_only_binds_a_matching_keyword() {
+        let mut layout = CanvasLayout::new(|_| {});
+        push_pending_hotplug_device(&mut layout, "Other Device");
+
+        assert_eq!(layout.reconnect_pending("Test Device").unwrap(), false);
+        assert!(matches!(layout.devices[0].canvas, DeviceBinding::Pending));
+
+        assert_eq!(layout.reconnect_pending("Other Device").unwrap(), true);
+        assert!(matches!(layout.devices[0].canvas, DeviceBinding::Bound(_)));
+    }
+
+    #[test]
+    fn disconnect_demotes_a_bound_device_and_reconnect_rebinds_it() {
+        let mut layout = CanvasLayout::new(|_| {});
+        push_pending_hotplug_device(&mut layout, "Test Device");
+        layout.reconnect_pending("Test Device").unwrap();
+        assert!(matches!(layout.devices[0].canvas, DeviceBinding::Bound(_)));
+
+        // A write is made (but not flushed) while it's bound...
+        *layout.low_level_get_pending_mut(0, 0).unwrap() = Color::RED;
+
+        assert!(layout.disconnect("Test Device"));
+        assert!(matches!(layout.devices[0].canvas, DeviceBinding::Pending));
+        // Disconnecting again has nothing left to demote.
+        assert!(!layout.disconnect("Test Device"));
+
+        // Flushing while pending doesn't panic or error - the write is just silently dropped.
+        layout.flush().unwrap();
+
+        // ...and is still there, ready to go out once the device reconnects.
+        assert_eq!(*layout.low_level_get_pending(0, 0).unwrap(), Color::RED);
+        assert!(layout.reconnect_pending("Test Device").unwrap());
+        assert!(matches!(layout.devices[0].canvas, DeviceBinding::Bound(_)));
+    }
+}
@@ -0,0 +1,143 @@
+/// A MIDI port appearing or disappearing, as reported by [`DeviceMonitor`].
+///
+/// `keyword` is the same substring that [`InputDevice::MIDI_DEVICE_KEYWORD`](crate::InputDevice::MIDI_DEVICE_KEYWORD)
+/// and [`OutputDevice::MIDI_DEVICE_KEYWORD`](crate::OutputDevice::MIDI_DEVICE_KEYWORD) use for guessing a device.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum DeviceEvent {
+	/// A MIDI port whose name contains `keyword` just appeared.
+	Connected { keyword: &'static str, port_name: String },
+	/// A MIDI port whose name contains `keyword` just disappeared.
+	Disconnected { keyword: &'static str, port_name: String },
+}
+
+/// Watches the system's MIDI ports for the given keywords appearing and disappearing, so a
+/// long-running program can react to a Launchpad being plugged in or unplugged instead of only
+/// ever looking for it once at startup.
+///
+/// Re-enumerates ports on a background thread every `poll_interval` and reports changes through
+/// the usual [`MsgPollingWrapper`](crate::MsgPollingWrapper) receiver.
+pub struct DeviceMonitor {
+	receiver: std::sync::mpsc::Receiver<DeviceEvent>,
+}
+
+impl DeviceMonitor {
+	/// Starts watching for the given device keywords, e.g.
+	/// `launchy::s::Input::MIDI_DEVICE_KEYWORD`.
+	pub fn new(keywords: impl IntoIterator<Item = &'static str>) -> Self {
+		Self::with_poll_interval(keywords, std::time::Duration::from_millis(500))
+	}
+
+	/// Like [`Self::new`], but with an explicit re-enumeration interval instead of the default
+	/// half second.
+	pub fn with_poll_interval(
+		keywords: impl IntoIterator<Item = &'static str>,
+		poll_interval: std::time::Duration,
+	) -> Self {
+		let keywords: Vec<&'static str> = keywords.into_iter().collect();
+		let (sender, receiver) = std::sync::mpsc::channel();
+
+		std::thread::spawn(move || {
+			let mut known_ports = std::collections::HashSet::new();
+
+			loop {
+				let current_ports = matching_ports(&keywords);
+
+				for port_name in current_ports.difference(&known_ports) {
+					let event = DeviceEvent::Connected {
+						keyword: matching_keyword(&keywords, port_name),
+						port_name: port_name.clone(),
+					};
+					if sender.send(event).is_err() {
+						return;
+					}
+				}
+
+				for port_name in known_ports.difference(&current_ports) {
+					let event = DeviceEvent::Disconnected {
+						keyword: matching_keyword(&keywords, port_name),
+						port_name: port_name.clone(),
+					};
+					if sender.send(event).is_err() {
+						return;
+					}
+				}
+
+				known_ports = current_ports;
+				std::thread::sleep(poll_interval);
+			}
+		});
+
+		Self { receiver }
+	}
+}
+
+impl crate::MsgPollingWrapper for DeviceMonitor {
+	type Message = DeviceEvent;
+
+	fn receiver(&self) -> &std::sync::mpsc::Receiver<Self::Message> {
+		&self.receiver
+	}
+}
+
+fn matching_keyword(keywords: &[&'static str], port_name: &str) -> &'static str {
+	keywords
+		.iter()
+		.find(|keyword| port_name.contains(**keyword))
+		.expect("port_name was only collected because it matched one of these keywords")
+}
+
+// A device like the Launchpad shows up as both a MIDI input port and a MIDI output port. Only
+// reporting it as `Connected` once both are visible avoids a race where `reconnect_pending` grabs
+// whichever direction enumerated first and then fails to open the other, leaving the slot stuck.
+fn matching_ports(keywords: &[&'static str]) -> std::collections::HashSet<String> {
+	let input_ports = matching_input_ports(keywords);
+	let output_ports = matching_output_ports(keywords);
+
+	input_ports.intersection(&output_ports).cloned().collect()
+}
+
+fn matching_input_ports(keywords: &[&'static str]) -> std::collections::HashSet<String> {
+	let midi_in = match midir::MidiInput::new(crate::APPLICATION_NAME) {
+		Ok(midi_in) => midi_in,
+		Err(_) => return std::collections::HashSet::new(),
+	};
+
+	midi_in
+		.ports()
+		.iter()
+		.filter_map(|port| midi_in.port_name(port).ok())
+		.filter(|port_name| keywords.iter().any(|keyword| port_name.contains(keyword)))
+		.collect()
+}
+
+fn matching_output_ports(keywords: &[&'static str]) -> std::collections::HashSet<String> {
+	let midi_out = match midir::MidiOutput::new(crate::APPLICATION_NAME) {
+		Ok(midi_out) => midi_out,
+		Err(_) => return std::collections::HashSet::new(),
+	};
+
+	midi_out
+		.ports()
+		.iter()
+		.filter_map(|port| midi_out.port_name(port).ok())
+		.filter(|port_name| keywords.iter().any(|keyword| port_name.contains(keyword)))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matching_keyword_picks_the_keyword_the_port_name_actually_contains() {
+		let keywords = ["Launchpad S", "Launchpad MK2"];
+		assert_eq!(matching_keyword(&keywords, "Launchpad MK2 MIDI 1"), "Launchpad MK2");
+		assert_eq!(matching_keyword(&keywords, "Launchpad S"), "Launchpad S");
+	}
+
+	#[test]
+	#[should_panic]
+	fn matching_keyword_panics_on_a_port_name_matching_none_of_the_keywords() {
+		matching_keyword(&["Launchpad S"], "Some Other Device");
+	}
+}
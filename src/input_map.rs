@@ -0,0 +1,228 @@
+use super::Button;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+/// Implemented by a device's `Message` type so [`MappedInput`] can pull button press/release
+/// events out of it, ignoring whatever other variants that `Message` has (e.g.
+/// `launchpad_s::Message::TextEndedOrLooped`).
+pub trait ButtonEvent {
+	/// Returns `Some((button, true))` for a press, `Some((button, false))` for a release, and
+	/// `None` for anything else.
+	fn button_event(&self) -> Option<(Button, bool)>;
+}
+
+/// A semantic input event produced by [`MappedInput`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Action<T> {
+	Pressed(T),
+	Released(T),
+}
+
+/// A mapping from concrete [`Button`]s to an application-defined semantic token `T`. Build one
+/// with [`ButtonMap::builder`].
+pub struct ButtonMap<T> {
+	mappings: Vec<(Button, T)>,
+}
+
+impl<T: Clone> ButtonMap<T> {
+	pub fn builder() -> ButtonMapBuilder<T> {
+		ButtonMapBuilder { mappings: Vec::new() }
+	}
+
+	fn get(&self, button: Button) -> Option<&T> {
+		self.mappings
+			.iter()
+			.find(|(mapped_button, _)| *mapped_button == button)
+			.map(|(_, token)| token)
+	}
+}
+
+/// Builds a [`ButtonMap`]. See [`ButtonMap::builder`].
+pub struct ButtonMapBuilder<T> {
+	mappings: Vec<(Button, T)>,
+}
+
+impl<T: Clone> ButtonMapBuilder<T> {
+	/// Maps a single button to `token`.
+	pub fn button(mut self, button: Button, token: T) -> Self {
+		self.mappings.push((button, token));
+		self
+	}
+
+	/// Maps the four [`Button`] arrows (`UP`/`DOWN`/`LEFT`/`RIGHT`) to the given directional
+	/// tokens.
+	pub fn arrows(self, up: T, down: T, left: T, right: T) -> Self {
+		self.button(Button::UP, up)
+			.button(Button::DOWN, down)
+			.button(Button::LEFT, left)
+			.button(Button::RIGHT, right)
+	}
+
+	/// Maps every grid button in the `width`x`height` rectangle starting at `(x, y)` to a single
+	/// `token`, e.g. to turn a 2x2 block of pads into one logical "big button".
+	pub fn region(mut self, x: u8, y: u8, width: u8, height: u8, token: T) -> Self {
+		for dx in 0..width {
+			for dy in 0..height {
+				self.mappings
+					.push((Button::GridButton { x: x + dx, y: y + dy }, token.clone()));
+			}
+		}
+		self
+	}
+
+	pub fn build(self) -> ButtonMap<T> {
+		ButtonMap { mappings: self.mappings }
+	}
+}
+
+/// Wraps any device's [`MsgPollingWrapper`](crate::MsgPollingWrapper) and translates its raw
+/// `Press`/`Release` messages into semantic [`Action`]s via a [`ButtonMap`], tracking which tokens
+/// are currently held along the way. Implements [`MsgPollingWrapper`](crate::MsgPollingWrapper)
+/// itself, same as [`CanvasLayoutPoller`](crate::CanvasLayoutPoller) and
+/// [`DeviceMonitor`](crate::DeviceMonitor) - poll it exactly the same way.
+///
+/// ```ignore
+/// # use launchy::prelude::*;
+/// # use launchy::input_map::*;
+/// // `poller` is whatever MsgPollingWrapper your device's `InputDevice` impl hands you, e.g.
+/// // `launchy::s::Input::guess_polling()?.1`.
+/// let map = ButtonMap::builder().arrows("up", "down", "left", "right").build();
+/// let mapped_input = MappedInput::new(poller, map);
+/// for action in mapped_input.receiver().iter() {
+///     println!("{:?}", action);
+/// }
+/// ```
+pub struct MappedInput<T: Clone + Eq + Hash + Send + 'static> {
+	receiver: std::sync::mpsc::Receiver<Action<T>>,
+	held: Arc<Mutex<HashSet<T>>>,
+}
+
+impl<T: Clone + Eq + Hash + Send + 'static> MappedInput<T> {
+	/// Spawns a background thread that reads `source`'s messages, translates the ones matching a
+	/// button in `map` into [`Action`]s, and makes them available through this object's own
+	/// [`MsgPollingWrapper`](crate::MsgPollingWrapper) receiver. Messages with no mapped button,
+	/// or that aren't a press/release to begin with, are silently dropped.
+	pub fn new<P>(source: P, map: ButtonMap<T>) -> Self
+	where
+		P: crate::MsgPollingWrapper + Send + 'static,
+		P::Message: ButtonEvent,
+	{
+		let (sender, receiver) = std::sync::mpsc::channel();
+		let held = Arc::new(Mutex::new(HashSet::new()));
+		let held_thread = held.clone();
+
+		std::thread::spawn(move || {
+			for message in source.receiver() {
+				let (button, pressed) = match message.button_event() {
+					Some(event) => event,
+					None => continue,
+				};
+				let token = match map.get(button) {
+					Some(token) => token.clone(),
+					None => continue,
+				};
+
+				let action = if pressed {
+					held_thread.lock().unwrap().insert(token.clone());
+					Action::Pressed(token)
+				} else {
+					held_thread.lock().unwrap().remove(&token);
+					Action::Released(token)
+				};
+
+				if sender.send(action).is_err() {
+					return;
+				}
+			}
+		});
+
+		Self { receiver, held }
+	}
+
+	/// Whether `token` is currently held, i.e. its button was pressed and hasn't been released
+	/// since.
+	pub fn is_held(&self, token: &T) -> bool {
+		self.held.lock().unwrap().contains(token)
+	}
+}
+
+impl<T: Clone + Eq + Hash + Send + 'static> crate::MsgPollingWrapper for MappedInput<T> {
+	type Message = Action<T>;
+
+	fn receiver(&self) -> &std::sync::mpsc::Receiver<Self::Message> {
+		&self.receiver
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MsgPollingWrapper;
+
+	#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+	enum TestMessage {
+		Button(Button, bool),
+		Other,
+	}
+
+	impl ButtonEvent for TestMessage {
+		fn button_event(&self) -> Option<(Button, bool)> {
+			match *self {
+				Self::Button(button, pressed) => Some((button, pressed)),
+				Self::Other => None,
+			}
+		}
+	}
+
+	struct TestSource {
+		receiver: std::sync::mpsc::Receiver<TestMessage>,
+	}
+
+	impl crate::MsgPollingWrapper for TestSource {
+		type Message = TestMessage;
+
+		fn receiver(&self) -> &std::sync::mpsc::Receiver<Self::Message> {
+			&self.receiver
+		}
+	}
+
+	#[test]
+	fn region_maps_every_button_in_the_rectangle_to_the_same_token() {
+		let map = ButtonMap::builder().region(0, 0, 2, 2, "big_button").build();
+
+		assert_eq!(map.get(Button::GridButton { x: 0, y: 0 }), Some(&"big_button"));
+		assert_eq!(map.get(Button::GridButton { x: 1, y: 1 }), Some(&"big_button"));
+		assert_eq!(map.get(Button::GridButton { x: 2, y: 0 }), None);
+	}
+
+	#[test]
+	fn earlier_mapping_wins_on_overlap() {
+		let map = ButtonMap::builder()
+			.button(Button::GridButton { x: 0, y: 0 }, "first")
+			.region(0, 0, 2, 2, "second")
+			.build();
+
+		assert_eq!(map.get(Button::GridButton { x: 0, y: 0 }), Some(&"first"));
+		assert_eq!(map.get(Button::GridButton { x: 1, y: 0 }), Some(&"second"));
+	}
+
+	#[test]
+	fn mapped_input_translates_messages_and_tracks_held_tokens() {
+		let (sender, receiver) = std::sync::mpsc::channel();
+		let source = TestSource { receiver };
+		let map = ButtonMap::builder().button(Button::UP, "up").build();
+		let mapped = MappedInput::new(source, map);
+
+		sender.send(TestMessage::Other).unwrap();
+		sender.send(TestMessage::Button(Button::DOWN, true)).unwrap(); // unmapped, dropped
+		sender.send(TestMessage::Button(Button::UP, true)).unwrap();
+
+		assert_eq!(mapped.receiver().recv().unwrap(), Action::Pressed("up"));
+		assert!(mapped.is_held(&"up"));
+
+		sender.send(TestMessage::Button(Button::UP, false)).unwrap();
+		assert_eq!(mapped.receiver().recv().unwrap(), Action::Released("up"));
+		assert!(!mapped.is_held(&"up"));
+	}
+}
@@ -12,6 +12,16 @@ pub enum Message {
 	UnknownShortMessage { bytes: [u8; 3] },
 }
 
+impl crate::input_map::ButtonEvent for Message {
+	fn button_event(&self) -> Option<(Button, bool)> {
+		match *self {
+			Message::Press { button } => Some((button, true)),
+			Message::Release { button } => Some((button, false)),
+			Message::TextEndedOrLooped | Message::UnknownShortMessage { .. } => None,
+		}
+	}
+}
+
 fn decode_grid_button(btn: u8) -> Button {
 	return Button::GridButton { x: btn % 16, y: btn / 16 };
 }
@@ -14,9 +14,20 @@ pub use color::*;
 mod canvas;
 pub use canvas::*;
 
+mod text;
+pub use text::*;
+
+mod transition;
+pub use transition::*;
+
 mod midi_io;
 pub use midi_io::*;
 
+mod device_monitor;
+pub use device_monitor::*;
+
+pub mod input_map;
+
 pub mod launchpad_s;
 pub use launchpad_s as s;
 
@@ -29,6 +40,7 @@ pub use launch_control as control;
 pub mod prelude {
 	pub use crate::midi_io::{OutputDevice, InputDevice, MsgPollingWrapper};
 	pub use crate::canvas::Canvas;
+	pub use crate::transition::CanvasSnapshotExt;
 }
 
 /// Identifier used for e.g. the midi port names etc.
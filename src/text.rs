@@ -0,0 +1,388 @@
+use crate::{Canvas, Color};
+
+/// One row-packed bitmap glyph. Each entry is one row of the glyph, and the low `width` bits
+/// (bit `width - 1` is the leftmost column) are the lit pixels in that row.
+type Glyph = &'static [u8];
+
+/// An embedded bitmap font usable with [`draw_text`] and [`TextScroller`].
+///
+/// Only `Normal` and `Small` are implemented for now - there's no `Bold` to go with them yet.
+/// Add a variant here plus its own glyph table if a bold/mono face turns out to be worth it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Font {
+	/// The default font: 5 pixels wide, 7 pixels tall.
+	Normal,
+	/// A narrower font for fitting more onto a single Launchpad: 3 pixels wide, 5 pixels tall.
+	Small,
+}
+
+impl Font {
+	/// Returns `(width, height)` of this font's glyphs, in pixels.
+	pub fn size(self) -> (u8, u8) {
+		match self {
+			Self::Normal => (5, 7),
+			Self::Small => (3, 5),
+		}
+	}
+
+	fn glyph(self, c: char) -> Glyph {
+		match self {
+			Self::Normal => normal_glyph(c.to_ascii_uppercase()),
+			Self::Small => small_glyph(c.to_ascii_uppercase()),
+		}
+	}
+}
+
+// Unmapped characters fall back to this - a lit box, so a typo in a string is obvious on the
+// Launchpad instead of silently vanishing.
+const UNKNOWN_NORMAL: Glyph = &[0b11111, 0b11111, 0b11111, 0b11111, 0b11111, 0b11111, 0b11111];
+const UNKNOWN_SMALL: Glyph = &[0b111, 0b111, 0b111, 0b111, 0b111];
+
+fn normal_glyph(c: char) -> Glyph {
+	match c {
+		' ' => &[0, 0, 0, 0, 0, 0, 0],
+		'0' => &[0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+		'1' => &[0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+		'2' => &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+		'3' => &[0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+		'4' => &[0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+		'5' => &[0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+		'6' => &[0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+		'7' => &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+		'8' => &[0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+		'9' => &[0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+		'A' => &[0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+		'B' => &[0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+		'C' => &[0b01111, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b01111],
+		'D' => &[0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+		'E' => &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+		'F' => &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+		'G' => &[0b01111, 0b10000, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+		'H' => &[0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+		'I' => &[0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+		'J' => &[0b00001, 0b00001, 0b00001, 0b00001, 0b10001, 0b10001, 0b01110],
+		'K' => &[0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+		'L' => &[0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+		'M' => &[0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+		'N' => &[0b10001, 0b11001, 0b10101, 0b10101, 0b10011, 0b10001, 0b10001],
+		'O' => &[0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+		'P' => &[0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+		'Q' => &[0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+		'R' => &[0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+		'S' => &[0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+		'T' => &[0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+		'U' => &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+		'V' => &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+		'W' => &[0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+		'X' => &[0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+		'Y' => &[0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+		'Z' => &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+		'.' => &[0, 0, 0, 0, 0, 0b01100, 0b01100],
+		',' => &[0, 0, 0, 0, 0, 0b01100, 0b01000],
+		'!' => &[0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0, 0b00100],
+		'?' => &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0, 0b00100],
+		':' => &[0, 0b01100, 0b01100, 0, 0b01100, 0b01100, 0],
+		'-' => &[0, 0, 0, 0b11111, 0, 0, 0],
+		_ => UNKNOWN_NORMAL,
+	}
+}
+
+fn small_glyph(c: char) -> Glyph {
+	match c {
+		' ' => &[0, 0, 0, 0, 0],
+		'0' => &[0b111, 0b101, 0b101, 0b101, 0b111],
+		'1' => &[0b010, 0b110, 0b010, 0b010, 0b111],
+		'2' => &[0b111, 0b001, 0b111, 0b100, 0b111],
+		'3' => &[0b111, 0b001, 0b111, 0b001, 0b111],
+		'4' => &[0b101, 0b101, 0b111, 0b001, 0b001],
+		'5' => &[0b111, 0b100, 0b111, 0b001, 0b111],
+		'6' => &[0b111, 0b100, 0b111, 0b101, 0b111],
+		'7' => &[0b111, 0b001, 0b010, 0b010, 0b010],
+		'8' => &[0b111, 0b101, 0b111, 0b101, 0b111],
+		'9' => &[0b111, 0b101, 0b111, 0b001, 0b111],
+		'A' => &[0b010, 0b101, 0b111, 0b101, 0b101],
+		'B' => &[0b110, 0b101, 0b110, 0b101, 0b110],
+		'C' => &[0b011, 0b100, 0b100, 0b100, 0b011],
+		'D' => &[0b110, 0b101, 0b101, 0b101, 0b110],
+		'E' => &[0b111, 0b100, 0b110, 0b100, 0b111],
+		'F' => &[0b111, 0b100, 0b110, 0b100, 0b100],
+		'G' => &[0b011, 0b100, 0b101, 0b101, 0b011],
+		'H' => &[0b101, 0b101, 0b111, 0b101, 0b101],
+		'I' => &[0b111, 0b010, 0b010, 0b010, 0b111],
+		'J' => &[0b001, 0b001, 0b001, 0b101, 0b010],
+		'K' => &[0b101, 0b101, 0b110, 0b101, 0b101],
+		'L' => &[0b100, 0b100, 0b100, 0b100, 0b111],
+		'M' => &[0b101, 0b111, 0b111, 0b101, 0b101],
+		'N' => &[0b101, 0b111, 0b111, 0b111, 0b101],
+		'O' => &[0b010, 0b101, 0b101, 0b101, 0b010],
+		'P' => &[0b110, 0b101, 0b110, 0b100, 0b100],
+		'Q' => &[0b010, 0b101, 0b101, 0b111, 0b011],
+		'R' => &[0b110, 0b101, 0b110, 0b101, 0b101],
+		'S' => &[0b011, 0b100, 0b010, 0b001, 0b110],
+		'T' => &[0b111, 0b010, 0b010, 0b010, 0b010],
+		'U' => &[0b101, 0b101, 0b101, 0b101, 0b010],
+		'V' => &[0b101, 0b101, 0b101, 0b101, 0b010],
+		'W' => &[0b101, 0b101, 0b111, 0b111, 0b101],
+		'X' => &[0b101, 0b101, 0b010, 0b101, 0b101],
+		'Y' => &[0b101, 0b101, 0b010, 0b010, 0b010],
+		'Z' => &[0b111, 0b001, 0b010, 0b100, 0b111],
+		'.' => &[0, 0, 0, 0, 0b010],
+		',' => &[0, 0, 0, 0b010, 0b100],
+		'!' => &[0b010, 0b010, 0b010, 0, 0b010],
+		'?' => &[0b110, 0b001, 0b010, 0, 0b010],
+		':' => &[0, 0b010, 0, 0b010, 0],
+		'-' => &[0, 0, 0b111, 0, 0],
+		_ => UNKNOWN_SMALL,
+	}
+}
+
+/// Draws `text` onto `canvas`, with its top-left corner at `(origin_x, origin_y)`, using
+/// [`Font::Normal`]. Pixels that would land outside `canvas`'s [`Canvas::bounding_box`], or that
+/// simply don't exist on it (e.g. a multi-device [`CanvasLayout`](crate::CanvasLayout) has gaps),
+/// are silently clipped, so it's safe to draw text that starts off-screen (as [`TextScroller`]
+/// does) or spans several differently-shaped devices.
+///
+/// If `bg` is `Some`, the glyphs' background cells are painted with it; if `None`, the background
+/// is left untouched, letting the text overlay whatever is already on the canvas.
+pub fn draw_text(
+	canvas: &mut impl Canvas,
+	text: &str,
+	origin_x: i32,
+	origin_y: i32,
+	fg: Color,
+	bg: Option<Color>,
+) {
+	draw_text_with_font(canvas, text, origin_x, origin_y, Font::Normal, 1, fg, bg);
+}
+
+/// Like [`draw_text`], but with an explicit [`Font`] and inter-glyph `spacing` (in pixels) instead
+/// of always using [`Font::Normal`] with a spacing of `1`.
+pub fn draw_text_with_font(
+	canvas: &mut impl Canvas,
+	text: &str,
+	origin_x: i32,
+	origin_y: i32,
+	font: Font,
+	spacing: i32,
+	fg: Color,
+	bg: Option<Color>,
+) {
+	let (glyph_width, glyph_height) = font.size();
+	let (canvas_width, canvas_height) = canvas.bounding_box();
+
+	let mut cursor_x = origin_x;
+	for c in text.chars() {
+		let glyph = font.glyph(c);
+
+		for row in 0..glyph_height {
+			for col in 0..glyph_width {
+				let x = cursor_x + col as i32;
+				let y = origin_y + row as i32;
+				if x < 0 || y < 0 || x as u32 >= canvas_width || y as u32 >= canvas_height {
+					continue;
+				}
+				let (x, y) = (x as u32, y as u32);
+
+				// `canvas`'s bounding box is a rectangle, but the canvas itself might not be -
+				// e.g. a Launchpad's control row is narrower than its grid rows. Skip coordinates
+				// that fall in the gap rather than unwrapping and panicking.
+				let pixel = match canvas.low_level_get_pending_mut(x, y) {
+					Some(pixel) => pixel,
+					None => continue,
+				};
+
+				let lit = (glyph[row as usize] >> (glyph_width - 1 - col)) & 1 != 0;
+				if lit {
+					*pixel = fg;
+				} else if let Some(bg) = bg {
+					*pixel = bg;
+				}
+			}
+		}
+
+		cursor_x += glyph_width as i32 + spacing;
+	}
+}
+
+/// Scrolls a string horizontally across a [`Canvas`], a column at a time.
+///
+/// Call [`Self::step`] once per flush to advance the marquee. This mirrors the hardware
+/// `Message::TextEndedOrLooped` semantics: [`Self::step`] returns `true` once the text has
+/// scrolled fully past and looped back to its starting position.
+pub struct TextScroller {
+	text: String,
+	font: Font,
+	spacing: i32,
+	fg: Color,
+	bg: Option<Color>,
+	offset: i32,
+}
+
+impl TextScroller {
+	/// Creates a new scroller, starting just past the right edge of the canvas it's first
+	/// [`step`](Self::step)ped on.
+	pub fn new(text: impl Into<String>, fg: Color, bg: Option<Color>) -> Self {
+		Self {
+			text: text.into(),
+			font: Font::Normal,
+			spacing: 1,
+			fg,
+			bg,
+			offset: 0,
+		}
+	}
+
+	/// Uses `font` instead of the default [`Font::Normal`].
+	pub fn with_font(mut self, font: Font) -> Self {
+		self.font = font;
+		self
+	}
+
+	/// Uses `spacing` pixels between glyphs instead of the default `1`.
+	pub fn with_spacing(mut self, spacing: i32) -> Self {
+		self.spacing = spacing;
+		self
+	}
+
+	fn text_width(&self) -> i32 {
+		let (glyph_width, _) = self.font.size();
+		self.text.chars().count() as i32 * (glyph_width as i32 + self.spacing)
+	}
+
+	/// Draws the current scroll position onto `canvas` at vertical position `y`, then advances
+	/// the offset by one column. Returns `true` once the text has scrolled all the way off the
+	/// left edge and looped back to its starting position.
+	pub fn step(&mut self, canvas: &mut impl Canvas, y: i32) -> bool {
+		let (canvas_width, _) = canvas.bounding_box();
+
+		draw_text_with_font(
+			canvas,
+			&self.text,
+			canvas_width as i32 - self.offset,
+			y,
+			self.font,
+			self.spacing,
+			self.fg,
+			self.bg,
+		);
+
+		self.offset += 1;
+
+		let looped = self.offset > self.text_width() + canvas_width as i32;
+		if looped {
+			self.offset = 0;
+		}
+		looped
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	/// A [`Canvas`] whose real pixels are only a subset of its rectangular bounding box, standing
+	/// in for a Launchpad-shaped device so clipping against missing pixels (not just off-grid
+	/// coordinates) can be exercised without real hardware.
+	struct TestCanvas {
+		width: u32,
+		height: u32,
+		pixels: HashMap<(u32, u32), Color>,
+	}
+
+	impl TestCanvas {
+		fn new(width: u32, height: u32, real_pixels: impl IntoIterator<Item = (u32, u32)>) -> Self {
+			let pixels = real_pixels.into_iter().map(|pos| (pos, Color::BLACK)).collect();
+			Self { width, height, pixels }
+		}
+	}
+
+	impl Canvas for TestCanvas {
+		fn lowest_visible_brightness(&self) -> f32 {
+			0.0
+		}
+
+		fn bounding_box(&self) -> (u32, u32) {
+			(self.width, self.height)
+		}
+
+		fn low_level_get_pending(&self, x: u32, y: u32) -> Option<&Color> {
+			self.pixels.get(&(x, y))
+		}
+
+		fn low_level_get_pending_mut(&mut self, x: u32, y: u32) -> Option<&mut Color> {
+			self.pixels.get_mut(&(x, y))
+		}
+
+		fn low_level_get(&self, x: u32, y: u32) -> Option<&Color> {
+			self.pixels.get(&(x, y))
+		}
+
+		fn flush(&mut self) -> Result<(), crate::MidiError> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn draw_text_clips_columns_that_fall_outside_the_canvas() {
+		// 3 wide, 5 tall - exactly one "1" glyph from Font::Small, with no room to spare.
+		let mut canvas = TestCanvas::new(3, 5, (0..3).flat_map(|x| (0..5).map(move |y| (x, y))));
+		draw_text_with_font(&mut canvas, "1AB", 0, 0, Font::Small, 1, Color::RED, None);
+
+		// "A" and "B" would start at x = 4 and x = 8 - entirely off the 3-wide canvas - so none of
+		// their pixels should have been written.
+		for (x, y) in (0..3).flat_map(|x| (0..5).map(move |y| (x, y))) {
+			if *canvas.low_level_get_pending(x, y).unwrap() == Color::RED {
+				assert!(x < 3, "unexpected lit pixel at ({}, {}) outside the first glyph", x, y);
+			}
+		}
+	}
+
+	#[test]
+	fn draw_text_skips_coordinates_that_are_not_real_pixels() {
+		// A 2x2 box, but only (0, 0) is a real pixel - mimicking a non-rectangular device. This
+		// must not panic even though the glyph covers the missing coordinates too.
+		let mut canvas = TestCanvas::new(2, 2, [(0, 0)]);
+		draw_text_with_font(&mut canvas, "1", 0, 0, Font::Small, 1, Color::RED, None);
+		assert!(canvas.low_level_get_pending(1, 1).is_none());
+	}
+
+	#[test]
+	fn text_scroller_reports_looped_once_the_text_has_fully_scrolled_off() {
+		let mut canvas = TestCanvas::new(8, 1, (0..8).map(|x| (x, 0)));
+		let mut scroller = TextScroller::new("1", Color::RED, None).with_font(Font::Small);
+
+		let text_width = 3 + 1; // Font::Small's width plus the default 1px spacing
+		let steps_until_loop = text_width + 8; // + canvas width, per `step`'s own loop condition
+
+		let mut loops = 0;
+		for _ in 0..steps_until_loop {
+			assert!(!scroller.step(&mut canvas, 0));
+		}
+		if scroller.step(&mut canvas, 0) {
+			loops += 1;
+		}
+		assert_eq!(loops, 1);
+	}
+
+	#[test]
+	fn text_scroller_spacing_widens_the_loop_period() {
+		let mut narrow = TextScroller::new("1", Color::RED, None).with_font(Font::Small);
+		let mut wide =
+			TextScroller::new("1", Color::RED, None).with_font(Font::Small).with_spacing(5);
+
+		let mut narrow_canvas = TestCanvas::new(8, 1, (0..8).map(|x| (x, 0)));
+		let mut wide_canvas = TestCanvas::new(8, 1, (0..8).map(|x| (x, 0)));
+
+		let mut narrow_steps = 0;
+		while !narrow.step(&mut narrow_canvas, 0) {
+			narrow_steps += 1;
+		}
+		let mut wide_steps = 0;
+		while !wide.step(&mut wide_canvas, 0) {
+			wide_steps += 1;
+		}
+
+		assert!(wide_steps > narrow_steps);
+	}
+}
@@ -0,0 +1,117 @@
+use crate::{Canvas, Color};
+
+/// A full snapshot of a [`Canvas`]'s pending colors, keyed by absolute `(x, y)` coordinates.
+/// Captured with [`CanvasSnapshotExt::snapshot`] and used as the `from`/`to` frame for
+/// [`Transition::play`].
+pub type ColorBuffer = std::collections::HashMap<(u32, u32), Color>;
+
+/// Extends every [`Canvas`] with the ability to capture its current frame as a [`ColorBuffer`],
+/// so it can be replayed later with [`Transition::play`].
+pub trait CanvasSnapshotExt: Canvas {
+	/// Reads every pixel currently pending on this canvas into a [`ColorBuffer`].
+	fn snapshot(&self) -> ColorBuffer {
+		let (width, height) = self.bounding_box();
+
+		let mut buffer = ColorBuffer::new();
+		for y in 0..height {
+			for x in 0..width {
+				if let Some(&color) = self.low_level_get_pending(x, y) {
+					buffer.insert((x, y), color);
+				}
+			}
+		}
+		buffer
+	}
+}
+
+impl<C: Canvas + ?Sized> CanvasSnapshotExt for C {}
+
+/// A visual effect for switching a [`Canvas`] from one full-grid frame to another over time. See
+/// [`Transition::play`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Transition {
+	/// Linearly blends every pixel from `from` to `to` at once: `from * (1.0 - t) + to * t`.
+	CrossFade,
+	/// Same linear blend as [`Self::CrossFade`]. Kept as a separate variant so callers can name
+	/// whichever fits the moment in their UI without it meaning anything different.
+	Dissolve,
+	/// Reveals `to` by sweeping a hard edge in from the left.
+	WipeRight,
+	/// Reveals `to` by sweeping a hard edge in from the right.
+	WipeLeft,
+	/// Reveals `to` by sweeping a hard edge in from the top.
+	SlideDown,
+	/// Reveals `to` by sweeping a hard edge in from the bottom.
+	SlideUp,
+}
+
+/// Linearly blends two colors: `t = 0.0` is `from`, `t = 1.0` is `to`, everything in between is a
+/// proportional mix of the two.
+fn lerp(from: Color, to: Color, t: f32) -> Color {
+	from * (1.0 - t) + to * t
+}
+
+impl Transition {
+	/// Draws one frame of this transition from `from` to `to` onto `canvas`, at progress `t`
+	/// (`0.0` is fully `from`, `1.0` is fully `to`). Call this across several flushes with
+	/// increasing `t` to animate the switch between two frames captured with
+	/// [`CanvasSnapshotExt::snapshot`].
+	pub fn play(self, canvas: &mut impl Canvas, from: &ColorBuffer, to: &ColorBuffer, t: f32) {
+		let t = t.clamp(0.0, 1.0);
+		let (width, height) = canvas.bounding_box();
+
+		for y in 0..height {
+			for x in 0..width {
+				// A device's pixels don't have to fill its whole bounding box - e.g. a Launchpad's
+				// control row is narrower than its grid rows - so `(x, y)` might not be a real
+				// pixel on `canvas` at all. Skip those instead of writing to a slot nothing reads.
+				if canvas.low_level_get_pending(x, y).is_none() {
+					continue;
+				}
+
+				let from_color = from.get(&(x, y)).copied().unwrap_or(Color::BLACK);
+				let to_color = to.get(&(x, y)).copied().unwrap_or(Color::BLACK);
+
+				let color = match self {
+					Self::CrossFade | Self::Dissolve => lerp(from_color, to_color, t),
+					Self::WipeRight => {
+						let boundary = (t * width as f32).round() as u32;
+						if x < boundary { to_color } else { from_color }
+					}
+					Self::WipeLeft => {
+						let boundary = width - (t * width as f32).round() as u32;
+						if x >= boundary { to_color } else { from_color }
+					}
+					Self::SlideDown => {
+						let boundary = (t * height as f32).round() as u32;
+						if y < boundary { to_color } else { from_color }
+					}
+					Self::SlideUp => {
+						let boundary = height - (t * height as f32).round() as u32;
+						if y >= boundary { to_color } else { from_color }
+					}
+				};
+
+				*canvas.low_level_get_pending_mut(x, y).unwrap() = color;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lerp_at_zero_is_from_and_at_one_is_to() {
+		assert_eq!(lerp(Color::BLACK, Color::RED, 0.0), Color::BLACK);
+		assert_eq!(lerp(Color::BLACK, Color::RED, 1.0), Color::RED);
+	}
+
+	#[test]
+	fn lerp_at_half_is_between_from_and_to() {
+		let mid = lerp(Color::BLACK, Color::RED, 0.5);
+		assert_ne!(mid, Color::BLACK);
+		assert_ne!(mid, Color::RED);
+	}
+}